@@ -1,24 +1,41 @@
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+
 use clap::{Parser, ValueEnum};
 
 use color_eyre::eyre::eyre;
 use color_eyre::eyre::Context;
 use color_eyre::eyre::Result;
 
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+
 use hickory_client::client::{Client, SyncClient};
 use hickory_client::op::ResponseCode;
+use hickory_client::proto::rr::dnssec::rdata::tsig::TsigAlgorithm;
+use hickory_client::proto::rr::dnssec::tsig::TSigner;
 use hickory_client::rr::{Name, RecordType};
 use hickory_client::tcp::TcpClientConnection;
 
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
 use hickory_resolver::error::ResolveError;
-use hickory_resolver::{error::ResolveErrorKind, Resolver};
+use hickory_resolver::proto::error::ProtoErrorKind;
+use hickory_resolver::system_conf::read_system_conf;
+use hickory_resolver::{error::ResolveErrorKind, TokioAsyncResolver};
 
 use owo_colors::{OwoColorize, Stream::Stdout};
 
 use regex::Regex;
 
+use serde::Serialize;
+
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 
+/// PTR lookup results for one address: the address itself, the names that pointed to it, and one
+/// PTR lookup result per configured resolver (in the same order as `resolvers`).
+type PtrResults = (IpAddr, Vec<String>, Vec<Result<Vec<String>, ResolveError>>);
+
 /// Check that all address records in a DNS zone have valid and acceptable PTR records associated
 #[derive(Clone, Debug, Parser)]
 #[command(
@@ -41,9 +58,47 @@ struct Arguments {
     /// Use colored output
     #[clap(short, long, value_enum, default_value_t=Color::Auto)]
     color: Color,
+    /// Maximum number of addresses to look up concurrently. Each address is queried against every
+    /// configured resolver at once, so with several --resolver options more lookups than this
+    /// can be in flight
+    #[arg(short = 'j', long, default_value_t = 16, value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+    /// Validate PTR answers with DNSSEC. A reverse zone that is signed but returns bogus
+    /// (failed-validation) data is reported as a distinct failure rather than being silently
+    /// treated as a valid PTR
+    #[arg(long)]
+    dnssec: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+    /// Forward-confirm PTRs: check that at least one of an address's PTR names resolves back to
+    /// that address
+    #[arg(short = 'f', long = "forward-confirm")]
+    forward_confirm: bool,
+    /// Regular expression that PTRs are expected to match; fails any PTR that does *not* match,
+    /// complementing --badre. Supports templated tokens derived from the address: {octet1}-
+    /// {octet4} for IPv4 (dotted-decimal octets in normal order) and {nibble0}-{nibble31} for
+    /// IPv6 (hex nibbles in normal, not reversed, order)
+    #[arg(short, long)]
+    goodre: Option<String>,
+    /// Reverse resolver to query for PTRs (in form "IP:port"; ":port" optional), repeatable.
+    /// Defaults to the system resolv.conf. When more than one is given, all are queried and
+    /// addresses whose PTR answers disagree between resolvers are flagged
+    #[arg(short = 'r', long = "resolver")]
+    resolver: Vec<String>,
     /// Server to do AXFR against (in form "IP:port"; ":port" optional)
     #[arg(short, long)]
     server: String,
+    /// TSIG algorithm to sign the AXFR request with
+    #[arg(long = "tsig-algorithm", default_value = "hmac-sha256")]
+    tsig_algorithm: String,
+    /// Base64-encoded TSIG key secret, for authenticating the AXFR. Must be given together with
+    /// --tsig-key-name
+    #[arg(long = "tsig-key")]
+    tsig_key: Option<String>,
+    /// TSIG key name, for authenticating the AXFR. Must be given together with --tsig-key
+    #[arg(long = "tsig-key-name")]
+    tsig_key_name: Option<String>,
     /// Be more verbose
     #[arg(short, long)]
     verbose: bool,
@@ -70,12 +125,55 @@ impl Color {
     }
 }
 
-fn main() -> Result<()> {
+#[derive(ValueEnum, Clone, Debug, PartialEq)]
+enum Format {
+    /// Colored, human-readable text on stdout (the default)
+    Human,
+    /// A single JSON object on stdout, for consumption by monitoring/CI
+    Json,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Ok,
+    Fail,
+}
+
+/// One structured record per address found in the zone, for `--format json`.
+#[derive(Serialize, Debug)]
+struct AddressReport {
+    ip: IpAddr,
+    names: Vec<String>,
+    ptrs: Vec<String>,
+    status: Status,
+    reason: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct Summary {
+    total: usize,
+    failed: usize,
+    ok_pct: f32,
+}
+
+#[derive(Serialize, Debug)]
+struct JsonOutput {
+    results: Vec<AddressReport>,
+    summary: Summary,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
     color_eyre::install()?;
 
     let args = Arguments::parse();
     args.clone().color.init();
 
+    // `--format json` is meant to be consumed by a machine, so it's the only thing written to
+    // stdout in that mode; all the human-readable chatter below is gated on this.
+    let is_human = args.format == Format::Human;
+
     // If we got a badre set in the arguments then best compile it now, both for performance and to
     // check it's actually a valid regexp.
     let mut re = None;
@@ -95,7 +193,7 @@ fn main() -> Result<()> {
 
     let zone = Name::from_utf8(&args.zone)?;
 
-    if args.verbose {
+    if args.verbose && is_human {
         println!(
             "Connecting to {} port {} for AXFR of zone {}",
             address.ip().if_supports_color(Stdout, |t| t.cyan()),
@@ -106,7 +204,7 @@ fn main() -> Result<()> {
 
     let seen_addresses = do_axfr(&args, address, zone)?;
 
-    if args.verbose {
+    if args.verbose && is_human {
         let num_addr_records = seen_addresses.keys().len();
         println!(
             "Found {} unique address (A/AAAA) record{}",
@@ -116,54 +214,284 @@ fn main() -> Result<()> {
     }
 
     let mut failcount: u64 = 0;
+    let mut json_reports: Vec<AddressReport> = Vec::new();
+
+    // Build the reverse resolver(s) up front and share them across every reverse lookup, rather
+    // than rebuilding one per address as before. With no `--resolver` given we fall back to the
+    // system resolv.conf, same as always; with one or more given, we build one resolver per
+    // server so that each can be queried independently and their answers compared.
+    let mut resolvers = Vec::new();
+    let mut resolver_labels = Vec::new();
+
+    if args.resolver.is_empty() {
+        // `tokio_from_system_conf` doesn't take a `ResolverOpts`, so read the system config
+        // ourselves when `--dnssec` needs to flip `validate` on.
+        let (config, mut opts) = read_system_conf()?;
+        opts.validate = args.dnssec;
+
+        resolvers.push(TokioAsyncResolver::tokio(config, opts));
+        resolver_labels.push("system resolv.conf".to_string());
+    } else {
+        for server in &args.resolver {
+            let server_addr = parse_socketaddr(server)?;
+            let group = NameServerConfigGroup::from_ips_clear(
+                &[server_addr.ip()],
+                server_addr.port(),
+                true,
+            );
+            let config = ResolverConfig::from_parts(None, Vec::new(), group);
+            let mut opts = ResolverOpts::default();
+            opts.validate = args.dnssec;
 
-    for (addr, names) in &seen_addresses {
-        if args.verbose {
+            resolvers.push(TokioAsyncResolver::tokio(config, opts));
+            resolver_labels.push(server_addr.to_string());
+        }
+    }
+
+    // Fan the lookups out through a bounded-concurrency pipeline so large zones don't resolve
+    // one address at a time. Each address is queried against every configured resolver.
+    let results: Vec<PtrResults> = stream::iter(seen_addresses.iter())
+        .map(|(addr, names)| {
+            let resolvers = &resolvers;
+            async move {
+                let per_resolver = join_all(resolvers.iter().map(|r| get_ptrs(r, addr))).await;
+                (*addr, names.clone(), per_resolver)
+            }
+        })
+        .buffer_unordered(args.concurrency.into())
+        .collect()
+        .await;
+
+    for (addr, names, per_resolver) in &results {
+        // Existing checks (missing/bad/good/forward-confirm) are all based on the first
+        // configured resolver's answers; disagreement between resolvers is reported separately
+        // below. `reasons` collects a plain-text entry per failure for `--format json`; it's
+        // built regardless of format, since it also doubles as the failure count.
+        let ptrs = &per_resolver[0];
+        let mut reasons: Vec<String> = Vec::new();
+
+        if args.verbose && is_human {
             list_names(addr, names);
         }
 
-        match get_ptrs(addr) {
+        // If a goodre was supplied it's a template, so it has to be expanded and compiled fresh
+        // for each address rather than once up front like badre.
+        let goodre_re = args
+            .goodre
+            .as_ref()
+            .map(|tpl| Regex::new(&expand_goodre_template(tpl, addr)))
+            .transpose()?;
+
+        match ptrs {
             Ok(ptrnames) => {
                 // There were 0 or more PTR names found.
                 // But actually were there any names?
                 if ptrnames.is_empty() {
                     // Always list the names when there's an error, but in verbose mode we have
                     // already listed them.
-                    if !args.verbose {
-                        list_names(addr, names);
+                    if is_human {
+                        if !args.verbose {
+                            list_names(addr, names);
+                        }
+                        println!(
+                            "    {} for {}",
+                            "Missing PTR".if_supports_color(Stdout, |t| t.bright_red()),
+                            addr.if_supports_color(Stdout, |t| t.cyan())
+                        );
                     }
-                    println!(
-                        "    {} for {}",
-                        "Missing PTR".if_supports_color(Stdout, |t| t.bright_red()),
-                        addr.if_supports_color(Stdout, |t| t.cyan())
-                    );
 
+                    reasons.push(format!("Missing PTR for {addr}"));
                     failcount += 1;
                 }
                 for ptr in ptrnames {
                     // If a badre was supplied then need to check PTR against that. We compiled it
                     // into `re` earlier.
                     if let Some(r) = &re {
-                        if let Some(_captures) = r.captures(&ptr) {
+                        if let Some(_captures) = r.captures(ptr) {
                             // This PTR matched the bad regex!
-                            if !args.verbose {
-                                list_names(addr, names);
+                            if is_human {
+                                if !args.verbose {
+                                    list_names(addr, names);
+                                }
+                                println!(
+                                    "    {} '{}' for {} (matched regexp '{}')",
+                                    "Bad PTR content".if_supports_color(Stdout, |t| t.bright_red()),
+                                    ptr.if_supports_color(Stdout, |t| t.bright_red()),
+                                    addr.if_supports_color(Stdout, |t| t.cyan()),
+                                    r.as_str().if_supports_color(Stdout, |t| t.cyan())
+                                );
                             }
+
+                            reasons.push(format!(
+                                "Bad PTR content: '{ptr}' for {addr} matched regexp '{}'",
+                                r.as_str()
+                            ));
+                            failcount += 1;
+                        }
+                    } else if args.verbose && is_human {
+                        // A PTR only reaches here once `get_ptrs` has returned `Ok`, so under
+                        // `--dnssec` it has already passed validation (a bogus answer is reported
+                        // separately, via the `Err` arm below).
+                        if args.dnssec {
+                            println!(
+                                "    {}: {ptr} ({})",
+                                "Found PTR".if_supports_color(Stdout, |t| t.green()),
+                                "secure".if_supports_color(Stdout, |t| t.green())
+                            );
+                        } else {
                             println!(
-                                "    {} '{}' for {} (matched regexp '{}')",
-                                "Bad PTR content".if_supports_color(Stdout, |t| t.bright_red()),
-                                ptr.if_supports_color(Stdout, |t| t.bright_red()),
-                                addr.if_supports_color(Stdout, |t| t.cyan()),
-                                r.as_str().if_supports_color(Stdout, |t| t.cyan())
+                                "    {}: {ptr}",
+                                "Found PTR".if_supports_color(Stdout, |t| t.green())
                             );
+                        }
+                    }
+
+                    // If a goodre was supplied then the PTR is expected to match it; anything
+                    // that doesn't is reported, complementing the badre check above.
+                    if let Some(goodre_re) = &goodre_re {
+                        if !goodre_re.is_match(ptr) {
+                            if is_human {
+                                if !args.verbose {
+                                    list_names(addr, names);
+                                }
+                                println!(
+                                    "    {} '{}' for {} (expected to match '{}')",
+                                    "Unexpected PTR".if_supports_color(Stdout, |t| t.bright_red()),
+                                    ptr.if_supports_color(Stdout, |t| t.bright_red()),
+                                    addr.if_supports_color(Stdout, |t| t.cyan()),
+                                    goodre_re.as_str().if_supports_color(Stdout, |t| t.cyan())
+                                );
+                            }
+
+                            reasons.push(format!(
+                                "Unexpected PTR: '{ptr}' for {addr} did not match expected pattern '{}'",
+                                goodre_re.as_str()
+                            ));
+                            failcount += 1;
+                        }
+                    }
+                }
+
+                // FCrDNS: a PTR that merely exists isn't enough, it should also forward-confirm,
+                // i.e. resolving the PTR name back gives us the address we started from. We only
+                // fail the address if *none* of its PTR names forward-confirm.
+                if args.forward_confirm && !ptrnames.is_empty() {
+                    let mut forward_results = Vec::with_capacity(ptrnames.len());
+
+                    for ptr in ptrnames {
+                        let confirmation = forward_confirm(&resolvers[0], addr, ptr).await;
+                        forward_results.push((ptr, confirmation));
+                    }
+
+                    let confirmed = forward_results.iter().any(|(_, r)| {
+                        r.as_ref()
+                            .map(|ips| ips.contains(addr))
+                            .unwrap_or(false)
+                    });
+
+                    if !confirmed {
+                        if is_human && !args.verbose {
+                            list_names(addr, names);
+                        }
+
+                        for (ptr, result) in forward_results {
+                            if is_human {
+                                let label = "Forward mismatch"
+                                    .if_supports_color(Stdout, |t| t.bright_red());
+                                let colored_ptr = ptr.if_supports_color(Stdout, |t| t.bright_red());
+                                let colored_addr = addr.if_supports_color(Stdout, |t| t.cyan());
+
+                                match &result {
+                                    Ok(ips) => {
+                                        let ips = ips
+                                            .iter()
+                                            .map(IpAddr::to_string)
+                                            .collect::<Vec<_>>()
+                                            .join(", ");
+                                        println!(
+                                            "    {label} '{colored_ptr}' for {colored_addr} (resolves to {ips})"
+                                        );
+                                    }
+                                    Err(e) => match e.kind() {
+                                        ResolveErrorKind::NoRecordsFound { .. } => {
+                                            println!(
+                                                "    {label} '{colored_ptr}' for {colored_addr} (no forward records)"
+                                            );
+                                        }
+                                        ResolveErrorKind::Timeout => {
+                                            println!(
+                                                "    {label} '{colored_ptr}' for {colored_addr} (forward lookup timed out)"
+                                            );
+                                        }
+                                        ResolveErrorKind::Proto(proto_err) => {
+                                            match dnssec_failure_kind(proto_err.kind()) {
+                                                Some(reason) => {
+                                                    println!(
+                                                        "    {label} '{colored_ptr}' for {colored_addr} (DNSSEC validation failed: {reason})"
+                                                    );
+                                                }
+                                                None => {
+                                                    println!(
+                                                        "    {label} '{colored_ptr}' for {colored_addr} (forward lookup error: {e})"
+                                                    );
+                                                }
+                                            }
+                                        }
+                                        _ => {
+                                            println!(
+                                                "    {label} '{colored_ptr}' for {colored_addr} (forward lookup error: {e})"
+                                            );
+                                        }
+                                    },
+                                }
+                            }
+
+                            match result {
+                                Ok(ips) => {
+                                    let ips = ips
+                                        .iter()
+                                        .map(IpAddr::to_string)
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                    reasons.push(format!(
+                                        "Forward mismatch: '{ptr}' for {addr} resolves to {ips}"
+                                    ));
+                                }
+                                Err(e) => match e.kind() {
+                                    ResolveErrorKind::NoRecordsFound { .. } => {
+                                        reasons.push(format!(
+                                            "Forward mismatch: '{ptr}' for {addr} has no forward records"
+                                        ));
+                                    }
+                                    ResolveErrorKind::Timeout => {
+                                        reasons.push(format!(
+                                            "Forward mismatch: '{ptr}' for {addr} forward lookup timed out"
+                                        ));
+                                    }
+                                    ResolveErrorKind::Proto(proto_err) => {
+                                        match dnssec_failure_kind(proto_err.kind()) {
+                                            Some(reason) => {
+                                                reasons.push(format!(
+                                                    "Forward mismatch: '{ptr}' for {addr} DNSSEC validation failed ({reason})"
+                                                ));
+                                            }
+                                            None => {
+                                                reasons.push(format!(
+                                                    "Forward mismatch: '{ptr}' for {addr} forward lookup error: {e}"
+                                                ));
+                                            }
+                                        }
+                                    }
+                                    _ => {
+                                        reasons.push(format!(
+                                            "Forward mismatch: '{ptr}' for {addr} forward lookup error: {e}"
+                                        ));
+                                    }
+                                },
+                            }
 
                             failcount += 1;
                         }
-                    } else if args.verbose {
-                        println!(
-                            "    {}: {ptr}",
-                            "Found PTR".if_supports_color(Stdout, |t| t.green())
-                        );
                     }
                 }
             }
@@ -171,24 +499,147 @@ fn main() -> Result<()> {
                 // There was a ResolveError.
                 match e.kind() {
                     ResolveErrorKind::Timeout => {
-                        if !args.verbose {
-                            list_names(addr, names);
+                        if is_human {
+                            if !args.verbose {
+                                list_names(addr, names);
+                            }
+                            println!(
+                                "    {}",
+                                "DNS resolution timeout".if_supports_color(Stdout, |t| t.bright_red())
+                            );
                         }
-                        println!(
-                            "    {}",
-                            "DNS resolution timeout".if_supports_color(Stdout, |t| t.bright_red())
-                        );
+                        reasons.push(format!("DNS resolution timeout for {addr}"));
                         failcount += 1;
                     }
+                    ResolveErrorKind::Proto(proto_err) => match dnssec_failure_kind(proto_err.kind()) {
+                        Some(reason) => {
+                            if is_human {
+                                if !args.verbose {
+                                    list_names(addr, names);
+                                }
+                                println!(
+                                    "    {} ({}) for {}",
+                                    "DNSSEC validation failed"
+                                        .if_supports_color(Stdout, |t| t.bright_red()),
+                                    reason.if_supports_color(Stdout, |t| t.bright_red()),
+                                    addr.if_supports_color(Stdout, |t| t.cyan())
+                                );
+                            }
+                            reasons.push(format!("DNSSEC validation failed ({reason}) for {addr}"));
+                            failcount += 1;
+                        }
+                        None => {
+                            panic!("Unhandled ResolveError {e:?} (should not happen");
+                        }
+                    },
                     _ => {
                         panic!("Unhandled ResolveError {e:?} (should not happen");
                     }
                 }
             }
         }
+
+        // When more than one resolver was configured, check they all agree on the PTRs for this
+        // address. Disagreement (e.g. a stale secondary or split-horizon misconfig) is reported
+        // separately from the checks above, which are all based on the first resolver's answers.
+        if resolvers.len() > 1 {
+            let normalized: Vec<Option<Vec<String>>> = per_resolver
+                .iter()
+                .map(|r| {
+                    r.as_ref().ok().map(|ptrs| {
+                        let mut names: Vec<String> = ptrs
+                            .iter()
+                            .map(|n| n.trim_end_matches('.').to_ascii_lowercase())
+                            .collect();
+                        names.sort();
+                        names.dedup();
+                        names
+                    })
+                })
+                .collect();
+
+            let all_agree = normalized.windows(2).all(|w| w[0] == w[1]);
+
+            if !all_agree {
+                if is_human {
+                    if !args.verbose {
+                        list_names(addr, names);
+                    }
+
+                    println!(
+                        "    {} for {}",
+                        "Resolvers disagree on PTR".if_supports_color(Stdout, |t| t.bright_red()),
+                        addr.if_supports_color(Stdout, |t| t.cyan())
+                    );
+
+                    for (label, result) in resolver_labels.iter().zip(per_resolver.iter()) {
+                        match result {
+                            Ok(ptrs) => println!("        {label}: {}", ptrs.join(", ")),
+                            Err(e) => println!("        {label}: error ({e})"),
+                        }
+                    }
+                }
+
+                let per_server = resolver_labels
+                    .iter()
+                    .zip(per_resolver.iter())
+                    .map(|(label, result)| match result {
+                        Ok(ptrs) => format!("{label}: {}", ptrs.join(", ")),
+                        Err(e) => format!("{label}: error ({e})"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+
+                reasons.push(format!("Resolvers disagree on PTR for {addr} ({per_server})"));
+                failcount += 1;
+            }
+        }
+
+        let status = if reasons.is_empty() {
+            Status::Ok
+        } else {
+            Status::Fail
+        };
+
+        json_reports.push(AddressReport {
+            ip: *addr,
+            names: names.clone(),
+            ptrs: ptrs.as_ref().ok().cloned().unwrap_or_default(),
+            status,
+            reason: (!reasons.is_empty()).then(|| reasons.join("; ")),
+        });
     }
 
-    if failcount > 0 {
+    // Per-address failure count, as opposed to `failcount` which is bumped once per *reason*
+    // (so a single address failing badre, goodre and forward-confirm all at once counts three
+    // times there). The "N% good PTRs" stats below are about addresses, not reasons, so they use
+    // this instead.
+    let failed_addresses = json_reports
+        .iter()
+        .filter(|r| r.status == Status::Fail)
+        .count();
+
+    if args.format == Format::Json {
+        let total = json_reports.len();
+        let ok_pct = if total > 0 {
+            (total - failed_addresses) as f32 / total as f32 * 100.0
+        } else {
+            0.0
+        };
+
+        let output = JsonOutput {
+            results: json_reports,
+            summary: Summary {
+                total,
+                failed: failed_addresses,
+                ok_pct,
+            },
+        };
+
+        println!("{}", serde_json::to_string(&output)?);
+    }
+
+    if failcount > 0 && is_human {
         let fire = emojis::get_by_shortcode("fire").unwrap();
         println!(
             "{} {} missing/broken PTR record{}",
@@ -198,18 +649,18 @@ fn main() -> Result<()> {
         );
     }
 
-    if args.verbose {
+    if args.verbose && is_human {
         let ok_pct: f32;
         let num_addr_records: usize = seen_addresses.keys().len();
 
         if num_addr_records > 0 {
-            if failcount == 0 {
+            if failed_addresses == 0 {
                 ok_pct = 100.0;
-            } else if failcount == num_addr_records as u64 {
+            } else if failed_addresses == num_addr_records {
                 ok_pct = 0.0;
             } else {
-                ok_pct =
-                    (num_addr_records as f32 - failcount as f32) / num_addr_records as f32 * 100.0;
+                ok_pct = (num_addr_records - failed_addresses) as f32 / num_addr_records as f32
+                    * 100.0;
             }
 
             let sparkles = emojis::get_by_shortcode("sparkles").unwrap();
@@ -284,16 +735,66 @@ fn list_names(addr: &IpAddr, names: &[String]) {
     println!("    {}", names.join(", "));
 }
 
+// Expand a `--goodre` template for a particular address, substituting `{octet1}`-`{octet4}` for
+// an IPv4 address's dotted-decimal octets, or `{nibble0}`-`{nibble31}` for an IPv6 address's hex
+// nibbles, in normal (not reversed/ip6.arpa) order. Tokens for the other address family, or that
+// don't apply (e.g. `{octet1}` for an IPv6 address), are left untouched.
+fn expand_goodre_template(template: &str, addr: &IpAddr) -> String {
+    let mut expanded = template.to_string();
+
+    match addr {
+        IpAddr::V4(v4) => {
+            for (i, octet) in v4.octets().iter().enumerate() {
+                expanded = expanded.replace(&format!("{{octet{}}}", i + 1), &octet.to_string());
+            }
+        }
+        IpAddr::V6(v6) => {
+            let hex: Vec<char> = v6
+                .octets()
+                .iter()
+                .flat_map(|byte| format!("{byte:02x}").chars().collect::<Vec<_>>())
+                .collect();
+
+            for (i, nibble) in hex.iter().enumerate() {
+                expanded = expanded.replace(&format!("{{nibble{i}}}"), &nibble.to_string());
+            }
+        }
+    }
+
+    expanded
+}
+
+// Classify a `ProtoErrorKind` surfaced by DNSSEC validation (`--dnssec`, i.e.
+// `ResolverOpts::validate`) as either a hard validation failure ("bogus") or a provably-unsigned
+// answer ("insecure"). Returns `None` for anything else, so callers can fall through to their
+// existing handling.
+fn dnssec_failure_kind(kind: &ProtoErrorKind) -> Option<&'static str> {
+    match kind {
+        ProtoErrorKind::RrsigsNotPresent { .. } => Some("insecure"),
+        ProtoErrorKind::Message(msg)
+            if *msg == "validation failed"
+                || *msg == "Could not validate all DNSKEYs"
+                || msg.starts_with("could not validate negative response") =>
+        {
+            Some("bogus")
+        }
+        _ => None,
+    }
+}
+
 // Return a Vec of strings for the found PTR names. Usually there will be just one, but it's
 // possible for there to be multiple. If there's none this will return an empty Vec. On any kind
 // of non-fatal resolver error (e.g. timeout) this will return a ResolveError.
-fn get_ptrs(addr: &IpAddr) -> Result<Vec<String>, ResolveError> {
-    // Construct a new Resolver using system's resolv.conf.
-    let resolver = Resolver::from_system_conf().unwrap();
-
+//
+// `resolver` is shared across every address being checked, so callers should build it once and
+// fan lookups out concurrently rather than constructing a new one per call.
+async fn get_ptrs(
+    resolver: &TokioAsyncResolver,
+    addr: &IpAddr,
+) -> Result<Vec<String>, ResolveError> {
     let mut ptrs = Vec::new();
 
-    match resolver.reverse_lookup(*addr) {
+    match resolver.reverse_lookup(*addr).await {
         Ok(response) => {
             for name in response.iter() {
                 ptrs.push(name.0.to_utf8());
@@ -306,8 +807,13 @@ fn get_ptrs(addr: &IpAddr) -> Result<Vec<String>, ResolveError> {
             ResolveErrorKind::Timeout => {
                 return Err(e);
             }
+            ResolveErrorKind::Proto(proto_err) if dnssec_failure_kind(proto_err.kind()).is_some() => {
+                return Err(e);
+            }
             _ => {
-                println!("Unhandled resolver error checking reverse for {addr}: {e:?}");
+                // Goes to stderr, not stdout, so it can't corrupt `--format json`'s single
+                // machine-readable line on stdout.
+                eprintln!("Unhandled resolver error checking reverse for {addr}: {e:?}");
             }
         },
     }
@@ -315,19 +821,95 @@ fn get_ptrs(addr: &IpAddr) -> Result<Vec<String>, ResolveError> {
     Ok(ptrs)
 }
 
+// Forward-confirm a PTR name: look up the address records (A for an IPv4 `addr`, AAAA for
+// IPv6) for `ptr` and return whatever IP addresses it resolves to, for the caller to check
+// `addr` is among them. PTR names carry a trailing dot and DNS names compare case-insensitively
+// anyway, so `ptr` is passed straight through without normalizing it first.
+async fn forward_confirm(
+    resolver: &TokioAsyncResolver,
+    addr: &IpAddr,
+    ptr: &str,
+) -> Result<Vec<IpAddr>, ResolveError> {
+    let record_type = if addr.is_ipv4() {
+        RecordType::A
+    } else {
+        RecordType::AAAA
+    };
+
+    let response = resolver.lookup(ptr, record_type).await?;
+
+    Ok(response.iter().filter_map(|rdata| rdata.ip_addr()).collect())
+}
+
+// Build a TSIG signer from `--tsig-key-name`/`--tsig-key`/`--tsig-algorithm`, for servers that
+// refuse unauthenticated AXFR. Returns `None` if no TSIG key was configured.
+fn build_tsig_signer(args: &Arguments) -> Result<Option<TSigner>> {
+    match (&args.tsig_key_name, &args.tsig_key) {
+        (Some(key_name), Some(key)) => {
+            let signer_name = Name::from_utf8(key_name)?;
+
+            let secret = BASE64_STANDARD
+                .decode(key)
+                .wrap_err("--tsig-key is not valid base64")?;
+
+            let algorithm = parse_tsig_algorithm(&args.tsig_algorithm)?;
+
+            let signer = TSigner::new(secret, algorithm, signer_name, 300)
+                .map_err(|e| eyre!("Invalid TSIG key: {e:?}"))?;
+
+            Ok(Some(signer))
+        }
+        (None, None) => Ok(None),
+        _ => Err(eyre!(
+            "--tsig-key-name and --tsig-key must be given together"
+        )),
+    }
+}
+
+fn parse_tsig_algorithm(algorithm: &str) -> Result<TsigAlgorithm> {
+    match algorithm.to_ascii_lowercase().as_str() {
+        "hmac-md5" => Ok(TsigAlgorithm::HmacMd5),
+        "hmac-sha1" => Ok(TsigAlgorithm::HmacSha1),
+        "hmac-sha224" => Ok(TsigAlgorithm::HmacSha224),
+        "hmac-sha256" => Ok(TsigAlgorithm::HmacSha256),
+        "hmac-sha384" => Ok(TsigAlgorithm::HmacSha384),
+        "hmac-sha512" => Ok(TsigAlgorithm::HmacSha512),
+        _ => Err(eyre!("Unsupported TSIG algorithm: {algorithm}")),
+    }
+}
+
 fn do_axfr(
     args: &Arguments,
     address: SocketAddr,
     zone: Name,
 ) -> Result<HashMap<IpAddr, Vec<String>>> {
-    let mut seen = HashMap::new();
-
     let conn = TcpClientConnection::new(address)?;
-    let client = SyncClient::new(conn);
+
+    match build_tsig_signer(args)? {
+        Some(signer) => {
+            let client = SyncClient::with_tsigner(conn, signer);
+            run_axfr(args, &client, address, &zone)
+        }
+        None => {
+            let client = SyncClient::new(conn);
+            run_axfr(args, &client, address, &zone)
+        }
+    }
+}
+
+// Drive the actual zone transfer and collect A/AAAA records into `seen`. Generic over the client
+// so that both a plain `SyncClient` and a TSIG-signed one (built in `do_axfr`) can share this.
+fn run_axfr<C: Client>(
+    args: &Arguments,
+    client: &C,
+    address: SocketAddr,
+    zone: &Name,
+) -> Result<HashMap<IpAddr, Vec<String>>> {
+    let mut seen = HashMap::new();
 
     // Specify `None` for `last_soa` to get whole zone content. If we specified an SOA this would
     // be an IXFR, but we need it all.
-    let response_stream = match client.zone_transfer(&zone, None) {
+    let response_stream = match client.zone_transfer(zone, None) {
         Ok(resp) => resp,
         Err(err) => {
             return Err(eyre!(
@@ -401,7 +983,7 @@ fn do_axfr(
         }
     }
 
-    if args.verbose {
+    if args.verbose && args.format == Format::Human {
         // AXFR always contains two SOA records, one at the sart and one at the end.
         num_records -= 2;
 
@@ -498,4 +1080,178 @@ mod tests {
 
         let _sa = parse_socketaddr(ip_port).unwrap();
     }
+
+    // Minimal `Arguments` for exercising `build_tsig_signer`; only the tsig_* fields vary between
+    // tests.
+    fn test_args(tsig_key_name: Option<&str>, tsig_key: Option<&str>) -> Arguments {
+        Arguments {
+            badre: None,
+            color: Color::Auto,
+            concurrency: 16,
+            dnssec: false,
+            format: Format::Human,
+            forward_confirm: false,
+            goodre: None,
+            resolver: Vec::new(),
+            server: "127.0.0.1:53".to_string(),
+            tsig_algorithm: "hmac-sha256".to_string(),
+            tsig_key: tsig_key.map(str::to_string),
+            tsig_key_name: tsig_key_name.map(str::to_string),
+            verbose: false,
+            zone: "example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn can_parse_each_supported_tsig_algorithm() {
+        for (name, algorithm) in [
+            ("hmac-md5", TsigAlgorithm::HmacMd5),
+            ("hmac-sha1", TsigAlgorithm::HmacSha1),
+            ("hmac-sha224", TsigAlgorithm::HmacSha224),
+            ("hmac-sha256", TsigAlgorithm::HmacSha256),
+            ("hmac-sha384", TsigAlgorithm::HmacSha384),
+            ("hmac-sha512", TsigAlgorithm::HmacSha512),
+        ] {
+            assert_eq!(parse_tsig_algorithm(name).unwrap(), algorithm);
+            // Matching is case-insensitive.
+            assert_eq!(parse_tsig_algorithm(&name.to_ascii_uppercase()).unwrap(), algorithm);
+        }
+    }
+
+    #[test]
+    fn errors_on_unsupported_tsig_algorithm() {
+        assert!(parse_tsig_algorithm("hmac-sha3-512").is_err());
+    }
+
+    #[test]
+    fn build_tsig_signer_is_none_with_no_key_given() {
+        let args = test_args(None, None);
+
+        assert!(build_tsig_signer(&args).unwrap().is_none());
+    }
+
+    #[test]
+    fn build_tsig_signer_errors_with_only_key_name_given() {
+        let args = test_args(Some("key."), None);
+
+        assert!(build_tsig_signer(&args).is_err());
+    }
+
+    #[test]
+    fn build_tsig_signer_errors_with_only_key_given() {
+        let args = test_args(None, Some("c2VjcmV0"));
+
+        assert!(build_tsig_signer(&args).is_err());
+    }
+
+    #[test]
+    fn build_tsig_signer_errors_on_invalid_base64_key() {
+        let args = test_args(Some("key."), Some("not valid base64!"));
+
+        assert!(build_tsig_signer(&args).is_err());
+    }
+
+    #[test]
+    fn build_tsig_signer_succeeds_with_both_given() {
+        let args = test_args(Some("key."), Some("c2VjcmV0"));
+
+        assert!(build_tsig_signer(&args).unwrap().is_some());
+    }
+
+    #[test]
+    fn expands_ipv4_octet_tokens_in_order() {
+        let addr: IpAddr = "10.20.30.40".parse().unwrap();
+
+        assert_eq!(
+            expand_goodre_template("^host-{octet1}-{octet2}-{octet3}-{octet4}\\.", &addr),
+            "^host-10-20-30-40\\."
+        );
+    }
+
+    #[test]
+    fn expands_ipv6_nibble_tokens_in_order() {
+        let addr: IpAddr = "2001:db8::1".parse().unwrap();
+
+        assert_eq!(
+            expand_goodre_template("^host-{nibble0}{nibble1}{nibble2}{nibble3}\\.", &addr),
+            "^host-2001\\."
+        );
+    }
+
+    #[test]
+    fn leaves_tokens_for_the_other_address_family_untouched() {
+        let v4: IpAddr = "10.20.30.40".parse().unwrap();
+        let v6: IpAddr = "2001:db8::1".parse().unwrap();
+
+        assert_eq!(
+            expand_goodre_template("{nibble0}-{octet1}", &v4),
+            "{nibble0}-10"
+        );
+        assert_eq!(
+            expand_goodre_template("{nibble0}-{octet1}", &v6),
+            "2-{octet1}"
+        );
+    }
+
+    #[test]
+    fn leaves_template_with_no_tokens_untouched() {
+        let addr: IpAddr = "10.20.30.40".parse().unwrap();
+
+        assert_eq!(expand_goodre_template("^host\\.example\\.com\\.$", &addr), "^host\\.example\\.com\\.$");
+    }
+
+    #[test]
+    fn classifies_missing_rrsigs_as_insecure() {
+        let kind = ProtoErrorKind::RrsigsNotPresent {
+            name: Name::from_utf8("example.com.").unwrap(),
+            record_type: RecordType::PTR,
+        };
+
+        assert_eq!(dnssec_failure_kind(&kind), Some("insecure"));
+    }
+
+    #[test]
+    fn classifies_known_validator_messages_as_bogus() {
+        assert_eq!(
+            dnssec_failure_kind(&ProtoErrorKind::Message("validation failed")),
+            Some("bogus")
+        );
+        assert_eq!(
+            dnssec_failure_kind(&ProtoErrorKind::Message("Could not validate all DNSKEYs")),
+            Some("bogus")
+        );
+        // NSEC-based negative-answer validation (the path a "no PTR record" answer from an
+        // NSEC3-signed reverse zone hits, since hickory 0.24 only supports NSEC).
+        assert_eq!(
+            dnssec_failure_kind(&ProtoErrorKind::Message(
+                "could not validate negative response with NSEC"
+            )),
+            Some("bogus")
+        );
+        assert_eq!(
+            dnssec_failure_kind(&ProtoErrorKind::Message(
+                "could not validate negative response missing SOA"
+            )),
+            Some("bogus")
+        );
+    }
+
+    #[test]
+    fn does_not_classify_unrelated_messages_as_bogus() {
+        // Regression test: these are real hickory-proto messages that happen to contain "valid"
+        // as a substring of "Invalid", but have nothing to do with DNSSEC.
+        assert_eq!(
+            dnssec_failure_kind(&ProtoErrorKind::Message("Invalid address length")),
+            None
+        );
+        assert_eq!(
+            dnssec_failure_kind(&ProtoErrorKind::Message("Invalid family type.")),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_classify_unrelated_error_kinds() {
+        assert_eq!(dnssec_failure_kind(&ProtoErrorKind::Timeout), None);
+    }
 }